@@ -0,0 +1,137 @@
+use std::io::Write;
+
+use pest::iterators::Pair;
+use thiserror::Error;
+
+use crate::{
+    operator,
+    pest::{Deserialize, Rule, Serialize},
+    transaction::Transaction,
+};
+
+/// A single `SecRule` action, e.g. `id:920100`, `deny`, or `msg:'SQL Injection Attack'`. `name`
+/// is the action's bare identifier; `arg` is everything after its `:`, if any.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Action {
+    pub name: String,
+    pub arg: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum ActionParseError {
+    #[error("invalid rule {0:?}")]
+    UnexpectedRule(Rule),
+}
+
+impl Action {
+    /// Whether this is the `capture` action, which gates whether a match populates the
+    /// reserved `TX:0`-`TX:9` slots.
+    pub fn is_capture(&self) -> bool {
+        self.name == "capture"
+    }
+
+    /// Applies this action's runtime effect to `tx`. Most CRS actions (`id`, `phase`, `msg`,
+    /// `tag`, `severity`, ...) only affect rule metadata and logging, which this crate doesn't
+    /// model, so they're no-ops here; `capture` is handled by the evaluator itself via
+    /// `is_capture` rather than here, since it has to take effect before the match is recorded.
+    pub fn execute(&self, _tx: &mut Transaction) {}
+}
+
+impl Deserialize for Action {
+    const RULE: Rule = Rule::action;
+    type Error = ActionParseError;
+
+    fn deserialize(input: Pair<Rule>) -> Result<Self, Self::Error> {
+        if input.as_rule() != Self::RULE {
+            return Err(ActionParseError::UnexpectedRule(input.as_rule()));
+        }
+
+        let mut inner = input.into_inner();
+        let name = inner.next().unwrap().as_str().to_owned();
+        let arg = inner.next().map(|p| unescape_arg(p.as_str()));
+
+        Ok(Self { name, arg })
+    }
+}
+
+/// Re-emits an `Action` in its canonical `name` or `name:arg` form. `arg` is escaped the same
+/// way `Operator::serialize` escapes its own argument -- plus a literal `,`, since `Vec<Action>`
+/// joins actions with `,` and an unescaped comma inside an arg would otherwise be read back as
+/// the start of a new action.
+impl Serialize for Action {
+    type Error = std::io::Error;
+
+    fn serialize<W: Write>(&self, mut out: W) -> Result<(), Self::Error> {
+        match &self.arg {
+            Some(arg) => write!(out, "{}:{}", self.name, escape_arg(arg)),
+            None => write!(out, "{}", self.name),
+        }
+    }
+}
+
+/// Escapes an action `arg` for safe embedding in the comma-joined `Vec<Action>` clause: quotes
+/// via `operator::escape`, plus a literal `,` (as `\,`) so it isn't mistaken for the separator
+/// between actions. `unescape_arg` is this function's exact inverse.
+fn escape_arg(arg: &str) -> String {
+    operator::escape(arg).replace(',', "\\,")
+}
+
+/// Inverse of `escape_arg`: undoes the `\,` produced there (in addition to the `\"` handled by
+/// `operator::unescape`), leaving every other backslash untouched.
+fn unescape_arg(arg: &str) -> String {
+    operator::unescape(arg).replace("\\,", ",")
+}
+
+impl Deserialize for Vec<Action> {
+    const RULE: Rule = Rule::actions;
+    type Error = ActionParseError;
+
+    fn deserialize(input: Pair<Rule>) -> Result<Self, Self::Error> {
+        if input.as_rule() != Self::RULE {
+            return Err(ActionParseError::UnexpectedRule(input.as_rule()));
+        }
+
+        let mut actions = Vec::new();
+        for action in input.into_inner() {
+            actions.push(Action::deserialize(action)?);
+        }
+
+        Ok(actions)
+    }
+}
+
+/// Re-emits a `Vec<Action>` as its canonical comma-joined `name[:arg],name[:arg],...` form.
+impl Serialize for Vec<Action> {
+    type Error = std::io::Error;
+
+    fn serialize<W: Write>(&self, mut out: W) -> Result<(), Self::Error> {
+        let mut first = true;
+        for action in self {
+            if first {
+                first = false;
+            } else {
+                write!(out, ",")?;
+            }
+            action.serialize(&mut out)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_arg_escapes_commas_and_quotes() {
+        assert_eq!(escape_arg("has a comma, here"), r"has a comma\, here");
+        assert_eq!(escape_arg(r#"has "quotes""#), r#"has \"quotes\""#);
+        assert_eq!(escape_arg(r#"has "quotes", and a comma"#), r#"has \"quotes\"\, and a comma"#);
+    }
+
+    #[test]
+    fn unescape_arg_is_the_inverse_of_escape_arg() {
+        let arg = r#"has "quotes", a comma, and \backslash"#;
+        assert_eq!(unescape_arg(&escape_arg(arg)), arg);
+    }
+}
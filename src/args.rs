@@ -0,0 +1,165 @@
+use crate::transaction::Transaction;
+
+/// The content type under which a request body is treated as `application/x-www-form-urlencoded`
+/// and parsed into `ARGS_POST`, matching ModSecurity's default `REQBODY_PROCESSOR` selection.
+const URLENCODED_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Populates `tx.args_get`/`tx.args_post` from the transaction's query string and body.
+///
+/// `QUERY_STRING` itself is left untouched -- ModSecurity always reports it raw, undecoded --
+/// but `ARGS_GET` is parsed from it regardless of content type. `ARGS_POST` is only populated
+/// when `Content-Type` is `application/x-www-form-urlencoded`; multipart bodies are handled
+/// separately by the `multipart` module, which appends its non-file parts to `args_post` itself.
+pub fn populate(tx: &mut Transaction) {
+    tx.args_get = parse_urlencoded(tx.query_string.as_bytes());
+
+    if content_type(&tx.content_type).eq_ignore_ascii_case(URLENCODED_CONTENT_TYPE) {
+        tx.args_post = parse_urlencoded(&tx.body);
+    }
+}
+
+/// The combined byte length of every decoded `ARGS_GET`/`ARGS_POST` value, i.e.
+/// `ARGS_COMBINED_SIZE`. Files are excluded, as they never contribute to `args_get`/`args_post`.
+///
+/// Measured off the already-decoded `String` values, which matches the real decoded byte length
+/// except when a value's raw decoded bytes aren't valid UTF-8 -- `parse_urlencoded` lossily
+/// replaces those with U+FFFD (3 bytes each) so they fit `MultiMap`'s `String` values, which can
+/// inflate the reported size for that narrower case. Known limitation, not expected to matter in
+/// practice since percent-encoding (decoded before this lossy step) is the normal way a request
+/// carries non-ASCII bytes.
+pub fn combined_size(tx: &Transaction) -> usize {
+    tx.args_get
+        .iter()
+        .chain(tx.args_post.iter())
+        .map(|(_, value)| value.len())
+        .sum()
+}
+
+/// Parses an `application/x-www-form-urlencoded` payload (or a raw query string) into an
+/// ordered list of decoded `(name, value)` pairs, mirroring the decoding performed by actix's
+/// `UrlEncoded` extractor and `serde_urlencoded`: the input is split on `&`, each pair is split
+/// on its first `=`, `+` is treated as a literal space, and both sides are percent-decoded.
+/// Repeated keys are preserved as separate entries rather than overwriting one another.
+///
+/// Operates on raw bytes throughout so that a percent-encoded non-UTF-8 payload (the exact thing
+/// percent-encoding exists to carry, and a pattern CRS rules target directly) survives splitting
+/// and decoding intact; the only lossy `String` conversion happens once per value, at the very
+/// end of `decode`, rather than on the whole input up front.
+pub fn parse_urlencoded(input: &[u8]) -> Vec<(String, String)> {
+    input
+        .split(|&b| b == b'&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.iter().position(|&b| b == b'=') {
+            Some(eq) => (decode(&pair[..eq]), decode(&pair[eq + 1..])),
+            None => (decode(pair), String::new()),
+        })
+        .collect()
+}
+
+/// Strips any `; charset=...`-style parameters off a `Content-Type` header before comparing it.
+fn content_type(header: &str) -> &str {
+    header.split(';').next().unwrap_or(header).trim()
+}
+
+fn decode(bytes: &[u8]) -> String {
+    let bytes: Vec<u8> = bytes.iter().map(|&b| if b == b'+' { b' ' } else { b }).collect();
+    String::from_utf8_lossy(&percent_decode(&bytes)).into_owned()
+}
+
+fn percent_decode(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..=i + 2]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_urlencoded_splits_on_ampersand_and_equals() {
+        assert_eq!(
+            parse_urlencoded(b"a=1&b=2"),
+            vec![("a".to_owned(), "1".to_owned()), ("b".to_owned(), "2".to_owned())]
+        );
+    }
+
+    #[test]
+    fn parse_urlencoded_preserves_repeated_keys() {
+        assert_eq!(
+            parse_urlencoded(b"id=1&id=2"),
+            vec![("id".to_owned(), "1".to_owned()), ("id".to_owned(), "2".to_owned())]
+        );
+    }
+
+    #[test]
+    fn parse_urlencoded_treats_plus_as_space() {
+        assert_eq!(
+            parse_urlencoded(b"q=a+b+c"),
+            vec![("q".to_owned(), "a b c".to_owned())]
+        );
+    }
+
+    #[test]
+    fn parse_urlencoded_handles_empty_and_missing_values() {
+        assert_eq!(
+            parse_urlencoded(b"a=&b"),
+            vec![("a".to_owned(), String::new()), ("b".to_owned(), String::new())]
+        );
+    }
+
+    #[test]
+    fn parse_urlencoded_ignores_empty_pairs() {
+        assert_eq!(parse_urlencoded(b"a=1&&b=2"), vec![("a".to_owned(), "1".to_owned()), ("b".to_owned(), "2".to_owned())]);
+        assert_eq!(parse_urlencoded(b""), Vec::<(String, String)>::new());
+    }
+
+    #[test]
+    fn percent_decode_decodes_hex_escapes() {
+        assert_eq!(percent_decode(b"a%20b"), b"a b");
+        assert_eq!(percent_decode(b"%3D"), b"=");
+    }
+
+    #[test]
+    fn percent_decode_leaves_invalid_escapes_untouched() {
+        assert_eq!(percent_decode(b"100%"), b"100%");
+        assert_eq!(percent_decode(b"%zz"), b"%zz");
+    }
+
+    #[test]
+    fn percent_decode_preserves_non_utf8_bytes_from_percent_escapes() {
+        // `%FF` is not valid UTF-8 on its own, but percent-encoding exists precisely to carry
+        // bytes like this -- they must survive as raw bytes, not get replaced with U+FFFD.
+        assert_eq!(percent_decode(b"%FF%FE"), vec![0xFF, 0xFE]);
+    }
+
+    #[test]
+    fn parse_urlencoded_keeps_values_either_side_of_a_raw_invalid_utf8_byte_distinct() {
+        // A literal (non-percent-encoded) invalid byte in one value must not corrupt the split
+        // of the surrounding `&`/`=`-delimited pairs, since those are found on raw bytes now.
+        let body = [b"a=".as_slice(), &[0xFF], b"&b=2".as_slice()].concat();
+        let parsed = parse_urlencoded(&body);
+        assert_eq!(parsed[0].0, "a");
+        assert_eq!(parsed[1], ("b".to_owned(), "2".to_owned()));
+    }
+
+    #[test]
+    fn content_type_strips_charset_parameter() {
+        assert_eq!(
+            content_type("application/x-www-form-urlencoded; charset=UTF-8"),
+            "application/x-www-form-urlencoded"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::transaction::Transaction;
+
+/// Populates `tx.cookies` by parsing the transaction's `Cookie` request header, if present.
+pub fn populate(tx: &mut Transaction) {
+    if let Some(header) = tx.header("Cookie") {
+        tx.cookies = parse(header);
+    }
+}
+
+/// Parses a `Cookie:` request header into an ordered list of `(name, value)` pairs, following
+/// the cookie-jar model used by Rocket's and salvo's `CookieJar`: split on `;`, trim surrounding
+/// whitespace, then split each pair on its first `=`. Duplicate names and empty values are kept
+/// as separate entries rather than merged, and a quoted value has its surrounding quotes
+/// stripped.
+pub fn parse(header: &str) -> Vec<(String, String)> {
+    header
+        .split(';')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((name, value)) => (name.trim().to_owned(), unquote(value.trim())),
+            None => (pair.to_owned(), String::new()),
+        })
+        .collect()
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_splits_on_semicolon_and_trims_whitespace() {
+        assert_eq!(
+            parse("a=1; b=2"),
+            vec![("a".to_owned(), "1".to_owned()), ("b".to_owned(), "2".to_owned())]
+        );
+    }
+
+    #[test]
+    fn parse_preserves_repeated_names() {
+        assert_eq!(
+            parse("id=1; id=2"),
+            vec![("id".to_owned(), "1".to_owned()), ("id".to_owned(), "2".to_owned())]
+        );
+    }
+
+    #[test]
+    fn parse_strips_surrounding_quotes_from_values() {
+        assert_eq!(parse(r#"session="abc123""#), vec![("session".to_owned(), "abc123".to_owned())]);
+    }
+
+    #[test]
+    fn parse_keeps_empty_values_as_separate_entries() {
+        assert_eq!(
+            parse("a=; b=2"),
+            vec![("a".to_owned(), String::new()), ("b".to_owned(), "2".to_owned())]
+        );
+    }
+
+    #[test]
+    fn parse_handles_a_cookie_with_no_equals_sign() {
+        assert_eq!(parse("flag"), vec![("flag".to_owned(), String::new())]);
+    }
+
+    #[test]
+    fn parse_ignores_empty_pairs() {
+        assert_eq!(parse("a=1;; b=2"), vec![("a".to_owned(), "1".to_owned()), ("b".to_owned(), "2".to_owned())]);
+        assert_eq!(parse(""), Vec::<(String, String)>::new());
+    }
+
+    #[test]
+    fn unquote_only_strips_matching_outer_quotes() {
+        assert_eq!(unquote(r#""quoted""#), "quoted");
+        assert_eq!(unquote("unquoted"), "unquoted");
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{
+    action::Action,
+    args,
+    input::{Input, InputType, Selector},
+    rule::SecRule,
+    transaction::Transaction,
+};
+
+/// The outcome of evaluating a single `SecRule` against a `Transaction`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MatchOutcome {
+    /// The rule's operator matched at least one resolved variable, and its actions ran.
+    Matched,
+    /// None of the rule's resolved variables matched the operator.
+    NoMatch,
+}
+
+/// A single resolved `(name, value)` pair produced while evaluating an `Input`. `name` is what
+/// gets recorded as `MATCHED_VAR_NAME`: the entry's key within its collection (e.g. `User-Agent`
+/// for a `REQUEST_HEADERS` entry), or the variable's own name for scalar variables and counts.
+/// The collection prefix (`REQUEST_HEADERS:`) is not included.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct ResolvedVar {
+    name: String,
+    value: String,
+}
+
+/// Evaluates `rule` against `tx`: resolves each of the rule's inputs to concrete values, runs
+/// them through the rule's `Operator`, and -- on a match -- updates `MATCHED_VAR`,
+/// `MATCHED_VAR_NAME`, `MATCHED_VARS`, `MATCHED_VARS_NAMES`, and (only when `rule.actions`
+/// carries the `capture` action, matching ModSecurity's `TX:0`-`TX:9` semantics) the reserved
+/// `TX:0`-`TX:9` collection, before executing the rule's `Action`s.
+///
+/// `Operator::matches` and `Action::execute` are the corresponding entry points on those types;
+/// they live alongside the operator/action parsers and are assumed here rather than redefined.
+pub fn evaluate(rule: &SecRule, tx: &mut Transaction) -> MatchOutcome {
+    tx.clear_matches();
+
+    let captures_tx = rule.actions.iter().any(Action::is_capture);
+
+    let mut matched = false;
+    for input in &rule.inputs {
+        for var in resolve(input, tx) {
+            if let Some(captures) = rule.op.matches(&var.value) {
+                matched = true;
+                tx.record_match(&var.name, &var.value);
+                if captures_tx {
+                    tx.set_captures(&captures);
+                }
+            }
+        }
+    }
+
+    if matched {
+        for action in &rule.actions {
+            action.execute(tx);
+        }
+        MatchOutcome::Matched
+    } else {
+        MatchOutcome::NoMatch
+    }
+}
+
+/// Resolves a single `Input` against `tx`, applying its `Selector` to the input type's
+/// underlying collection.
+fn resolve(input: &Input, tx: &Transaction) -> Vec<ResolvedVar> {
+    let values = collection(input.input, tx);
+    apply_selector(
+        input.input.name(),
+        &input.selector,
+        values,
+        input.input.case_insensitive_selector(),
+    )
+}
+
+/// Returns the raw, unfiltered `(name, value)` pairs backing an `InputType`: scalar variables
+/// like `REQUEST_METHOD` yield a single pair, collections like `REQUEST_HEADERS` yield one pair
+/// per entry. `ARGS_GET`/`ARGS_POST` are resolved from the fields populated by the `args`
+/// module, and `FILES`/`FILES_NAMES`/`FILES_COMBINED_SIZE`/`MULTIPART_PART_HEADERS` from the
+/// fields populated by the `multipart` module. Collections backed by parsers this crate hasn't
+/// added yet resolve to nothing until their module lands.
+fn collection(input: InputType, tx: &Transaction) -> Vec<(String, String)> {
+    use InputType::*;
+
+    match input {
+        RequestMethod => vec![(RequestMethod.name().into(), tx.method.clone())],
+        RequestUri => vec![(RequestUri.name().into(), tx.uri.clone())],
+        QueryString => vec![(QueryString.name().into(), tx.query_string.clone())],
+        RequestBody => vec![(
+            RequestBody.name().into(),
+            String::from_utf8_lossy(&tx.body).into_owned(),
+        )],
+        RequestHeaders => tx.headers.clone(),
+        RequestHeadersNames => names(&tx.headers),
+        RequestCookies => tx.cookies.clone(),
+        RequestCookiesNames => names(&tx.cookies),
+        Tx => tx.tx.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        MatchedVar => single(MatchedVar, tx.matched_var()),
+        MatchedVarName => single(MatchedVarName, tx.matched_var_name()),
+        MatchedVars => tagged(MatchedVars, tx.matched_vars()),
+        MatchedVarsNames => tagged(MatchedVarsNames, tx.matched_vars_names()),
+        ResponseStatus => tx
+            .response_status
+            .map(|s| (ResponseStatus.name().into(), s.to_string()))
+            .into_iter()
+            .collect(),
+        ResponseBody => vec![(
+            ResponseBody.name().into(),
+            String::from_utf8_lossy(&tx.response_body).into_owned(),
+        )],
+        ArgsGet => tx.args_get.clone(),
+        ArgsGetNames => names(&tx.args_get),
+        ArgsPost => tx.args_post.clone(),
+        ArgsPostNames => names(&tx.args_post),
+        Args => tx.args_get.iter().chain(tx.args_post.iter()).cloned().collect(),
+        ArgsNames => names(&tx.args_get).into_iter().chain(names(&tx.args_post)).collect(),
+        ArgsCombinedSize => vec![(
+            ArgsCombinedSize.name().into(),
+            args::combined_size(tx).to_string(),
+        )],
+        Files => tx.files.clone(),
+        FilesNames => names(&tx.files),
+        FilesCombinedSize => vec![(
+            FilesCombinedSize.name().into(),
+            tx.files_combined_size.to_string(),
+        )],
+        MultipartPartHeaders => tx.multipart_part_headers.clone(),
+        _ => Vec::new(),
+    }
+}
+
+fn names(entries: &[(String, String)]) -> Vec<(String, String)> {
+    entries.iter().map(|(k, _)| (k.clone(), k.clone())).collect()
+}
+
+fn single(input: InputType, value: Option<&str>) -> Vec<(String, String)> {
+    value
+        .map(|v| (input.name().into(), v.to_owned()))
+        .into_iter()
+        .collect()
+}
+
+fn tagged(input: InputType, values: &[String]) -> Vec<(String, String)> {
+    values.iter().map(|v| (input.name().into(), v.clone())).collect()
+}
+
+/// Applies a `Selector` to an input's resolved `(name, value)` pairs: `Include`/`Exclude` keep
+/// or drop entries by key, `Count` replaces the result with the number of entries matching a
+/// key, and `CountAll` replaces it with the total entry count. `case_insensitive` controls how
+/// `name`s are compared against the selector's key, per `InputType::case_insensitive_selector`.
+fn apply_selector(
+    var_name: &str,
+    selector: &Selector,
+    values: Vec<(String, String)>,
+    case_insensitive: bool,
+) -> Vec<ResolvedVar> {
+    let key_matches = |name: &str, key: &str| {
+        if case_insensitive {
+            name.eq_ignore_ascii_case(key)
+        } else {
+            name == key
+        }
+    };
+
+    match selector {
+        Selector::None => values.into_iter().map(ResolvedVar::from).collect(),
+        Selector::Include(key) => values
+            .into_iter()
+            .filter(|(name, _)| key_matches(name, key))
+            .map(ResolvedVar::from)
+            .collect(),
+        Selector::Exclude(key) => values
+            .into_iter()
+            .filter(|(name, _)| !key_matches(name, key))
+            .map(ResolvedVar::from)
+            .collect(),
+        Selector::Count(key) => {
+            let count = values.iter().filter(|(name, _)| key_matches(name, key)).count();
+            vec![ResolvedVar {
+                name: var_name.to_owned(),
+                value: count.to_string(),
+            }]
+        }
+        Selector::CountAll => vec![ResolvedVar {
+            name: var_name.to_owned(),
+            value: values.len().to_string(),
+        }],
+    }
+}
+
+impl From<(String, String)> for ResolvedVar {
+    fn from((name, value): (String, String)) -> Self {
+        Self { name, value }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operator::Operator;
+
+    fn pairs() -> Vec<(String, String)> {
+        vec![
+            ("id".to_owned(), "1".to_owned()),
+            ("name".to_owned(), "admin".to_owned()),
+            ("id".to_owned(), "2".to_owned()),
+        ]
+    }
+
+    #[test]
+    fn include_keeps_only_matching_keys() {
+        let resolved = apply_selector("ARGS", &Selector::Include("id".to_owned()), pairs(), false);
+        assert_eq!(
+            resolved,
+            vec![
+                ResolvedVar::from(("id".to_owned(), "1".to_owned())),
+                ResolvedVar::from(("id".to_owned(), "2".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn exclude_drops_matching_keys() {
+        let resolved = apply_selector("ARGS", &Selector::Exclude("id".to_owned()), pairs(), false);
+        assert_eq!(resolved, vec![ResolvedVar::from(("name".to_owned(), "admin".to_owned()))]);
+    }
+
+    #[test]
+    fn count_reports_number_of_matching_keys() {
+        let resolved = apply_selector("ARGS", &Selector::Count("id".to_owned()), pairs(), false);
+        assert_eq!(resolved, vec![ResolvedVar::from(("ARGS".to_owned(), "2".to_owned()))]);
+    }
+
+    #[test]
+    fn count_all_reports_total_entry_count() {
+        let resolved = apply_selector("ARGS", &Selector::CountAll, pairs(), false);
+        assert_eq!(resolved, vec![ResolvedVar::from(("ARGS".to_owned(), "3".to_owned()))]);
+    }
+
+    #[test]
+    fn case_sensitive_selector_does_not_match_differing_case() {
+        let resolved = apply_selector("ARGS_GET", &Selector::Include("ID".to_owned()), pairs(), false);
+        assert!(resolved.is_empty());
+    }
+
+    #[test]
+    fn case_insensitive_selector_matches_differing_case() {
+        let resolved = apply_selector("ARGS_GET", &Selector::Include("ID".to_owned()), pairs(), true);
+        assert_eq!(resolved.len(), 2);
+    }
+
+    #[test]
+    fn resolve_matches_args_get_case_sensitively() {
+        let mut tx = Transaction::new();
+        tx.args_get = vec![("id".to_owned(), "1".to_owned()), ("ID".to_owned(), "2".to_owned())];
+
+        let input = Input {
+            input: InputType::ArgsGet,
+            selector: Selector::Include("id".to_owned()),
+        };
+        let resolved = resolve(&input, &tx);
+        assert_eq!(resolved, vec![ResolvedVar::from(("id".to_owned(), "1".to_owned()))]);
+    }
+
+    #[test]
+    fn resolve_matches_request_headers_case_insensitively() {
+        let mut tx = Transaction::new();
+        tx.headers = vec![("User-Agent".to_owned(), "curl".to_owned())];
+
+        let input = Input {
+            input: InputType::RequestHeaders,
+            selector: Selector::Include("user-agent".to_owned()),
+        };
+        let resolved = resolve(&input, &tx);
+        assert_eq!(resolved, vec![ResolvedVar::from(("User-Agent".to_owned(), "curl".to_owned()))]);
+    }
+
+    fn rule(op: Operator) -> SecRule {
+        SecRule {
+            inputs: vec![Input {
+                input: InputType::ArgsGet,
+                selector: Selector::Include("id".to_owned()),
+            }],
+            op,
+            actions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn evaluate_reports_match_and_records_matched_var() {
+        let mut tx = Transaction::new();
+        tx.args_get = vec![("id".to_owned(), "920100".to_owned())];
+
+        let mut rule = rule(Operator::new(false, "rx".to_owned(), r"^\d+$".to_owned()));
+        rule.actions.push(Action {
+            name: "capture".to_owned(),
+            arg: None,
+        });
+
+        assert_eq!(evaluate(&rule, &mut tx), MatchOutcome::Matched);
+        assert_eq!(tx.matched_var(), Some("920100"));
+        assert_eq!(tx.matched_var_name(), Some("id"));
+        assert_eq!(tx.tx.get("0").map(String::as_str), Some("920100"));
+    }
+
+    #[test]
+    fn evaluate_reports_no_match_when_operator_never_matches() {
+        let mut tx = Transaction::new();
+        tx.args_get = vec![("id".to_owned(), "not-a-number".to_owned())];
+
+        let rule = rule(Operator::new(false, "rx".to_owned(), r"^\d+$".to_owned()));
+
+        assert_eq!(evaluate(&rule, &mut tx), MatchOutcome::NoMatch);
+        assert_eq!(tx.matched_var(), None);
+        assert!(tx.tx.is_empty());
+    }
+}
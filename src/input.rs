@@ -145,6 +145,17 @@ pub struct Input {
     pub selector: Selector,
 }
 
+impl InputType {
+    /// Whether this collection's keys should be matched case-insensitively when a `Selector`
+    /// filters by name. ModSecurity treats HTTP header names case-insensitively (per HTTP
+    /// semantics), but `ARGS`/`REQUEST_COOKIES`/`FILES`/`TX` keys are compared exactly as they
+    /// appeared on the wire -- `ARGS_GET:id` must not also match a parameter literally named
+    /// `ID`.
+    pub fn case_insensitive_selector(self) -> bool {
+        matches!(self, Self::RequestHeaders | Self::RequestHeadersNames)
+    }
+}
+
 enum_token! {
     #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
     pub enum SelectorType {
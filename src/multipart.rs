@@ -0,0 +1,267 @@
+use crate::transaction::Transaction;
+
+/// The content type under which a request body is treated as `multipart/form-data` and parsed
+/// via `populate`, matching ModSecurity's default `REQBODY_PROCESSOR` selection.
+const MULTIPART_CONTENT_TYPE: &str = "multipart/form-data";
+
+/// Extracts the `boundary` parameter out of a `Content-Type` header, e.g.
+/// `multipart/form-data; boundary=----WebKitFormBoundary7MA4YWxk` ->
+/// `----WebKitFormBoundary7MA4YWxk`. Returns `None` for any content type other than
+/// `multipart/form-data`, or if no `boundary` parameter is present.
+pub fn boundary(content_type: &str) -> Option<String> {
+    let mut params = content_type.split(';');
+    if !params.next()?.trim().eq_ignore_ascii_case(MULTIPART_CONTENT_TYPE) {
+        return None;
+    }
+
+    params.find_map(|param| {
+        let (name, value) = param.trim().split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("boundary")
+            .then(|| value.trim().trim_matches('"').to_owned())
+    })
+}
+
+/// A single parsed `multipart/form-data` part: its header lines, exactly as they appeared
+/// (including both the header name and value), the `name=`/`filename=` parameters pulled from
+/// its `Content-Disposition` header, and its body.
+struct Part {
+    header_lines: Vec<String>,
+    name: String,
+    filename: Option<String>,
+    body: Vec<u8>,
+}
+
+/// Parses a `multipart/form-data` body for the given `boundary`, populating `tx.files`,
+/// `tx.files_combined_size`, and `tx.multipart_part_headers`. Parts with no `filename=`
+/// parameter are not file uploads -- they're appended to `tx.args_post` instead, matching how
+/// ModSecurity folds plain multipart form fields into `ARGS_POST`.
+///
+/// The split/parse approach mirrors servo's file-manager and salvo's `FormData`/`FilePart`:
+/// split the body on the boundary delimiter, then split each part into its CRLF-terminated
+/// header block and body.
+pub fn populate(tx: &mut Transaction, boundary: &str, body: &[u8]) {
+    for part in parse_parts(boundary, body) {
+        for line in &part.header_lines {
+            tx.multipart_part_headers
+                .push((part.name.clone(), line.clone()));
+        }
+
+        match part.filename {
+            Some(filename) => {
+                tx.files.push((part.name, filename));
+                tx.files_combined_size += part.body.len();
+            }
+            // Lossy here, same known limitation as `args::parse_urlencoded`'s decoded values:
+            // a non-file part's body isn't percent-encoded, so invalid UTF-8 bytes in it are
+            // replaced with U+FFFD rather than preserved exactly.
+            None => tx
+                .args_post
+                .push((part.name, String::from_utf8_lossy(&part.body).into_owned())),
+        }
+    }
+}
+
+/// Defensive upper bound on the body this parser will attempt to split on the boundary.
+/// `find_delimiter` compares the whole `--boundary` at every byte offset it scans (resuming one
+/// byte past each rejected candidate), so splitting costs up to O(body length * boundary
+/// length) -- an uploaded file packed with near-miss
+/// `--boundary`-shaped byte sequences is a realistic CPU-DoS vector for a parser that runs on
+/// every request. Bodies over this size are treated as having no parts rather than parsed
+/// partially or truncated.
+const MAX_BODY_LEN: usize = 16 * 1024 * 1024;
+
+fn parse_parts(boundary: &str, body: &[u8]) -> Vec<Part> {
+    if body.len() > MAX_BODY_LEN {
+        return Vec::new();
+    }
+
+    let delimiter = format!("--{boundary}").into_bytes();
+    split(body, &delimiter)
+        .filter(|chunk| !chunk.is_empty())
+        .filter_map(parse_part)
+        .collect()
+}
+
+/// Splits `haystack` on every occurrence of `needle` that's a real RFC 2046 delimiter,
+/// analogous to `[u8]::split` but for a multi-byte delimiter. A bare substring match isn't
+/// enough -- file content can legitimately contain the boundary's bytes -- so `find_delimiter`
+/// requires each match to actually be anchored per the RFC.
+fn split<'a>(haystack: &'a [u8], needle: &'a [u8]) -> impl Iterator<Item = &'a [u8]> {
+    let mut rest = Some(haystack);
+    let mut at_start = true;
+    std::iter::from_fn(move || {
+        let haystack = rest?;
+        match find_delimiter(haystack, needle, at_start) {
+            Some(pos) => {
+                at_start = false;
+                let (chunk, remainder) = haystack.split_at(pos);
+                rest = Some(&remainder[needle.len()..]);
+                Some(chunk)
+            }
+            None => {
+                rest = None;
+                Some(haystack)
+            }
+        }
+    })
+}
+
+/// Finds the next occurrence of `needle` (`--boundary`) that's a genuine RFC 2046 delimiter
+/// rather than a coincidental byte sequence inside a part's (possibly binary) body: it must be
+/// preceded by `\r\n` -- or be at offset 0, which only the delimiter opening the very first
+/// part is allowed to be -- and followed by `\r\n` (more headers/parts follow) or `--` (the
+/// closing delimiter's trailing dashes). Without this anchoring, an uploaded file whose content
+/// happens to contain `--boundary` bytes could be used to smuggle payload past a part boundary.
+fn find_delimiter(haystack: &[u8], needle: &[u8], allow_start: bool) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(rel) = find(&haystack[offset..], needle) {
+        let pos = offset + rel;
+        let preceded = (allow_start && pos == 0) || (pos >= 2 && &haystack[pos - 2..pos] == b"\r\n");
+        let after = pos + needle.len();
+        let followed = haystack[after..].starts_with(b"\r\n") || haystack[after..].starts_with(b"--");
+        if preceded && followed {
+            return Some(pos);
+        }
+        offset = pos + 1;
+    }
+    None
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Parses a single part's CRLF-delimited header block and body. Parts that don't carry a
+/// `Content-Disposition: ... name="..."` header (the closing `--boundary--` delimiter, stray
+/// preamble/epilogue) are skipped -- but a part whose headers merely contain invalid UTF-8 (e.g.
+/// a crafted `filename=`) is not: `from_utf8_lossy` keeps it visible to `FILES`/`FILES_NAMES`/
+/// `MULTIPART_PART_HEADERS`/`ARGS_POST` rather than silently dropping the whole part, which would
+/// otherwise be an easy way to hide a file upload from every rule that inspects it.
+fn parse_part(chunk: &[u8]) -> Option<Part> {
+    let chunk = trim_crlf(chunk);
+    let header_end = find(chunk, b"\r\n\r\n")?;
+    let (header_block, rest) = chunk.split_at(header_end);
+    let body = rest[4..].to_vec();
+
+    let header_lines: Vec<String> = String::from_utf8_lossy(header_block)
+        .split("\r\n")
+        .filter(|line| !line.is_empty())
+        .map(str::to_owned)
+        .collect();
+
+    let disposition = header_lines
+        .iter()
+        .find(|line| line.to_ascii_lowercase().starts_with("content-disposition"))?;
+    let name = disposition_param(disposition, "name")?;
+    let filename = disposition_param(disposition, "filename");
+
+    Some(Part {
+        header_lines,
+        name,
+        filename,
+        body,
+    })
+}
+
+fn trim_crlf(chunk: &[u8]) -> &[u8] {
+    let chunk = chunk.strip_prefix(b"\r\n").unwrap_or(chunk);
+    chunk.strip_suffix(b"\r\n").unwrap_or(chunk)
+}
+
+/// Extracts a `Content-Disposition` parameter, e.g. `name="field"` or `filename=report.pdf`,
+/// handling both quoted and unquoted values.
+fn disposition_param(line: &str, param: &str) -> Option<String> {
+    let prefix = format!("{param}=");
+    line.split(';').map(str::trim).find_map(|field| {
+        let value = field.strip_prefix(&prefix)?;
+        Some(value.trim_matches('"').to_owned())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `--boundary`-shaped byte sequence inside a file's body must not be treated as a part
+    /// delimiter: it's preceded by part content, not a `\r\n`, and split() must pass it through
+    /// as payload so the full file is captured rather than truncated and leaking the rest as an
+    /// orphan, unparseable chunk.
+    #[test]
+    fn split_ignores_boundary_bytes_inside_file_content() {
+        let body = b"--X\r\n\
+            Content-Disposition: form-data; name=\"f\"; filename=\"evil.bin\"\r\n\
+            \r\n\
+            HELLO--X_HIDDEN_PAYLOAD_AFTER_FAKE_BOUNDARY\r\n\
+            --X--\r\n";
+
+        let parts = parse_parts("X", body);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            parts[0].body,
+            b"HELLO--X_HIDDEN_PAYLOAD_AFTER_FAKE_BOUNDARY".to_vec()
+        );
+    }
+
+    #[test]
+    fn split_still_separates_real_parts() {
+        let body = b"--X\r\na\r\n--X\r\nb\r\n--X--\r\n";
+        let chunks: Vec<_> = split(body, b"--X").collect();
+        assert_eq!(chunks, vec![&b""[..], &b"\r\na\r\n"[..], &b"\r\nb\r\n"[..], &b"--\r\n"[..]]);
+    }
+
+    #[test]
+    fn disposition_param_handles_quoted_and_unquoted_values() {
+        let line = r#"Content-Disposition: form-data; name="field"; filename=report.pdf"#;
+        assert_eq!(disposition_param(line, "name").as_deref(), Some("field"));
+        assert_eq!(disposition_param(line, "filename").as_deref(), Some("report.pdf"));
+    }
+
+    #[test]
+    fn parts_without_filename_are_not_files() {
+        let body = b"--X\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nvalue\r\n--X--\r\n";
+        let parts = parse_parts("X", body);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].filename, None);
+        assert_eq!(parts[0].name, "a");
+    }
+
+    #[test]
+    fn repeated_field_names_produce_separate_parts() {
+        let body = b"--X\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n1\r\n\
+            --X\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n2\r\n--X--\r\n";
+        let parts = parse_parts("X", body);
+        assert_eq!(parts.len(), 2);
+        assert!(parts.iter().all(|p| p.name == "a"));
+        assert_eq!(parts[0].body, b"1".to_vec());
+        assert_eq!(parts[1].body, b"2".to_vec());
+    }
+
+    #[test]
+    fn boundary_extracts_parameter_from_content_type() {
+        assert_eq!(
+            boundary("multipart/form-data; boundary=----WebKitFormBoundary7MA4YWxk"),
+            Some("----WebKitFormBoundary7MA4YWxk".to_owned())
+        );
+        assert_eq!(boundary("application/x-www-form-urlencoded"), None);
+    }
+
+    #[test]
+    fn invalid_utf8_in_a_header_does_not_drop_the_whole_part() {
+        let mut body = b"--X\r\nContent-Disposition: form-data; name=\"f\"; filename=\"".to_vec();
+        body.extend_from_slice(&[0xFF, 0xFE]);
+        body.extend_from_slice(b"\"\r\n\r\nbody\r\n--X--\r\n");
+
+        let parts = parse_parts("X", &body);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].name, "f");
+        assert!(parts[0].filename.is_some());
+        assert_eq!(parts[0].body, b"body".to_vec());
+    }
+
+    #[test]
+    fn bodies_over_the_size_cap_yield_no_parts() {
+        let body = vec![b'a'; MAX_BODY_LEN + 1];
+        assert!(parse_parts("X", &body).is_empty());
+    }
+}
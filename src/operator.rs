@@ -0,0 +1,265 @@
+use std::{io::Write, sync::OnceLock};
+
+use pest::iterators::Pair;
+use thiserror::Error;
+
+use crate::pest::{Deserialize, Rule, Serialize};
+
+/// A `SecRule` operator clause, e.g. `@rx ^/admin` or `!@eq 0`: an optional negation, the bare
+/// operator name (`rx`, `eq`, `pm`, ...), and its raw, operator-specific argument.
+///
+/// `regex_cache` lazily holds the compiled `@rx` pattern (`None` for every other operator, or if
+/// `arg` fails to compile) so that `matches` -- called once per resolved variable per rule, i.e.
+/// on every request for a ruleset evaluated live -- doesn't recompile the pattern from scratch
+/// each time. It's excluded from `Clone`/`Eq`/`Hash`, which only ever compare the logical
+/// `negate`/`name`/`arg` triple.
+pub struct Operator {
+    pub negate: bool,
+    pub name: String,
+    pub arg: String,
+    regex_cache: OnceLock<Option<regex::Regex>>,
+}
+
+impl std::fmt::Debug for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Operator")
+            .field("negate", &self.negate)
+            .field("name", &self.name)
+            .field("arg", &self.arg)
+            .finish()
+    }
+}
+
+impl Operator {
+    pub fn new(negate: bool, name: String, arg: String) -> Self {
+        Self {
+            negate,
+            name,
+            arg,
+            regex_cache: OnceLock::new(),
+        }
+    }
+}
+
+impl Clone for Operator {
+    fn clone(&self) -> Self {
+        Self::new(self.negate, self.name.clone(), self.arg.clone())
+    }
+}
+
+impl PartialEq for Operator {
+    fn eq(&self, other: &Self) -> bool {
+        (self.negate, &self.name, &self.arg) == (other.negate, &other.name, &other.arg)
+    }
+}
+
+impl Eq for Operator {}
+
+impl std::hash::Hash for Operator {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.negate.hash(state);
+        self.name.hash(state);
+        self.arg.hash(state);
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum OperatorParseError {
+    #[error("invalid rule {0:?}")]
+    UnexpectedRule(Rule),
+}
+
+impl Deserialize for Operator {
+    const RULE: Rule = Rule::operator;
+    type Error = OperatorParseError;
+
+    fn deserialize(input: Pair<Rule>) -> Result<Self, Self::Error> {
+        if input.as_rule() != Self::RULE {
+            return Err(OperatorParseError::UnexpectedRule(input.as_rule()));
+        }
+
+        let mut inner = input.into_inner();
+        let negate = match inner.peek() {
+            Some(p) if p.as_rule() == Rule::operator_negate => {
+                inner.next();
+                true
+            }
+            _ => false,
+        };
+
+        let name = inner.next().unwrap().as_str().to_owned();
+        let arg = inner
+            .next()
+            .map(|p| unescape(p.as_str()))
+            .unwrap_or_default();
+
+        Ok(Self::new(negate, name, arg))
+    }
+}
+
+/// Re-emits an `Operator` in its canonical `[!]@name[ arg]` form. `arg` is escaped (`"` only)
+/// since it's always serialized inside the surrounding double quotes of a `SecRule` line, the
+/// same way `Input`'s `Serialize` leaves the quoting to its caller.
+impl Serialize for Operator {
+    type Error = std::io::Error;
+
+    fn serialize<W: Write>(&self, mut out: W) -> Result<(), Self::Error> {
+        if self.negate {
+            write!(out, "!")?;
+        }
+        write!(out, "@{}", self.name)?;
+        if !self.arg.is_empty() {
+            write!(out, " {}", escape(&self.arg))?;
+        }
+        Ok(())
+    }
+}
+
+/// Escapes a literal `"` as `\"` so a value stays well-formed once embedded inside the
+/// double-quoted operator/action clause of a serialized `SecRule`. Shared with `Action`'s
+/// `Serialize`, which embeds its own `arg` the same way. Deliberately leaves `\` alone: CRS
+/// operator args are overwhelmingly `@rx` regexes (`\d`, `\s`, `\w`, `\.`, ...) where a bare
+/// backslash is never itself an escape introducer, so blindly doubling it would change the
+/// regex's meaning on reparse. `unescape` is this function's exact inverse.
+pub(crate) fn escape(arg: &str) -> String {
+    arg.replace('"', "\\\"")
+}
+
+/// Inverse of `escape`: turns an embedded `\"` back into a literal `"`, leaving every other
+/// backslash untouched (since `escape` never produces one). Applied in `Deserialize` so that
+/// `arg` always holds the same logical value whether it came from parsing or was constructed
+/// directly, which is what `Serialize`'s escaping assumes.
+pub(crate) fn unescape(arg: &str) -> String {
+    arg.replace("\\\"", "\"")
+}
+
+impl Operator {
+    /// Evaluates this operator against a single resolved variable value, returning the `@rx`
+    /// capture groups on a match (empty for operators that don't capture), or `None` if the
+    /// operator didn't match -- including when `name` isn't one of the operators implemented
+    /// below. CRS leans on operators (`@eq`, `@ge`, `@pm`, `@within`, `@detectSQLi`, ...) this
+    /// crate doesn't implement yet; treating those as a match/no-match guess would produce
+    /// silently wrong results for a WAF, so an unrecognized operator simply never matches.
+    ///
+    /// `!@op` negation is applied last, as in ModSecurity: a negated operator "matches" (and
+    /// its rule's actions run) when the underlying check doesn't.
+    pub fn matches(&self, value: &str) -> Option<Vec<String>> {
+        let hit = match self.name.as_str() {
+            "rx" => self
+                .compiled_regex()?
+                .captures(value)
+                .map(|caps| {
+                    caps.iter()
+                        .map(|m| m.map(|m| m.as_str().to_owned()).unwrap_or_default())
+                        .collect()
+                }),
+            "streq" => (value == self.arg).then(Vec::new),
+            "beginsWith" => value.starts_with(self.arg.as_str()).then(Vec::new),
+            "endsWith" => value.ends_with(self.arg.as_str()).then(Vec::new),
+            "contains" => value.contains(self.arg.as_str()).then(Vec::new),
+            _ => return None,
+        };
+
+        match (hit, self.negate) {
+            (Some(captures), false) => Some(captures),
+            (None, true) => Some(Vec::new()),
+            _ => None,
+        }
+    }
+
+    /// Returns the `@rx` pattern compiled from `arg`, compiling and caching it on first use.
+    /// `matches` is called once per resolved variable per rule on every evaluated transaction, so
+    /// without this cache a live ruleset would recompile every `@rx` pattern on every request.
+    fn compiled_regex(&self) -> Option<&regex::Regex> {
+        self.regex_cache
+            .get_or_init(|| regex::Regex::new(&self.arg).ok())
+            .as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(name: &str, arg: &str) -> Operator {
+        Operator::new(false, name.to_owned(), arg.to_owned())
+    }
+
+    #[test]
+    fn rx_returns_capture_groups_on_match() {
+        let captures = op("rx", r"id=(\d+)").matches("id=920100").unwrap();
+        assert_eq!(captures, vec!["id=920100".to_owned(), "920100".to_owned()]);
+    }
+
+    #[test]
+    fn rx_returns_none_on_no_match() {
+        assert_eq!(op("rx", r"^\d+$").matches("abc"), None);
+    }
+
+    #[test]
+    fn rx_reuses_the_compiled_regex_across_calls() {
+        let rule = op("rx", r"^\d+$");
+        assert!(rule.matches("1").is_some());
+        // Second call hits the cached `Regex` rather than recompiling `arg`.
+        assert!(rule.matches("2").is_some());
+        assert_eq!(rule.matches("abc"), None);
+    }
+
+    #[test]
+    fn rx_with_an_invalid_pattern_never_matches_and_stays_cached_as_none() {
+        let rule = op("rx", "(unclosed");
+        assert_eq!(rule.matches("anything"), None);
+        assert_eq!(rule.matches("anything"), None);
+    }
+
+    #[test]
+    fn equality_and_hash_ignore_the_regex_cache() {
+        let cold = op("rx", r"^\d+$");
+        let warm = op("rx", r"^\d+$");
+        warm.matches("1");
+        assert_eq!(cold, warm);
+
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        let hash = |op: &Operator| {
+            let mut hasher = DefaultHasher::new();
+            op.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&cold), hash(&warm));
+    }
+
+    #[test]
+    fn streq_begins_ends_contains_match_exactly() {
+        assert_eq!(op("streq", "admin").matches("admin"), Some(Vec::new()));
+        assert_eq!(op("streq", "admin").matches("administrator"), None);
+        assert_eq!(op("beginsWith", "/admin").matches("/admin/users"), Some(Vec::new()));
+        assert_eq!(op("endsWith", ".php").matches("index.php"), Some(Vec::new()));
+        assert_eq!(op("contains", "union select").matches("1 union select 1"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn unrecognized_operator_never_matches() {
+        assert_eq!(op("detectSQLi", "").matches("anything"), None);
+    }
+
+    #[test]
+    fn negation_inverts_the_match() {
+        let mut rule = op("streq", "admin");
+        rule.negate = true;
+        assert_eq!(rule.matches("admin"), None);
+        assert_eq!(rule.matches("guest"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn escape_only_touches_quotes_not_regex_backslashes() {
+        assert_eq!(escape(r"^\d+\s*$"), r"^\d+\s*$");
+        assert_eq!(escape(r#"has "quotes""#), r#"has \"quotes\""#);
+    }
+
+    #[test]
+    fn unescape_is_the_inverse_of_escape() {
+        let arg = r#"has "quotes" and \backslash and \d"#;
+        assert_eq!(unescape(&escape(arg)), arg);
+    }
+}
@@ -18,3 +18,18 @@ pub trait Deserialize: Sized {
 
     fn deserialize(input: Pair<Rule>) -> Result<Self, Self::Error>;
 }
+
+/// Parses `input` against `T::RULE` and deserializes the resulting top-level pair, for tests
+/// that need to exercise a real parse -> serialize -> reparse round trip rather than hand-build
+/// a `Pair` through `Deserialize` directly.
+#[cfg(test)]
+pub(crate) fn parse<T: Deserialize>(input: &str) -> T {
+    use pest::Parser;
+
+    let pair = CRSParser::parse(T::RULE, input)
+        .unwrap_or_else(|e| panic!("failed to parse {input:?} as {:?}: {e}", T::RULE))
+        .next()
+        .expect("grammar guarantees exactly one top-level pair per RULE");
+
+    T::deserialize(pair).unwrap_or_else(|e| panic!("failed to deserialize {input:?}: {e}"))
+}
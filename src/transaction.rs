@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+/// An ordered list of `(name, value)` pairs, used for headers, cookies, and request
+/// parameters. Kept as a `Vec` rather than a map so that repeated keys (duplicate headers,
+/// multi-valued form fields) are preserved exactly as they appeared on the wire.
+pub type MultiMap = Vec<(String, String)>;
+
+/// A single HTTP transaction: the request (and, once available, response) data a `SecRule`
+/// is evaluated against. Modeled on the request objects exposed by the actix and salvo web
+/// layers, trimmed down to the fields CRS rules actually reference.
+#[derive(Debug, Clone, Default)]
+pub struct Transaction {
+    pub method: String,
+    /// The request URI as sent, including any query string, e.g. `/index.php?p=X`; resolved
+    /// as-is for `REQUEST_URI`.
+    pub uri: String,
+    /// The raw, un-decoded query string (everything after `?`).
+    pub query_string: String,
+    pub headers: MultiMap,
+    pub cookies: MultiMap,
+    pub body: Vec<u8>,
+    /// The request's `Content-Type` header, used to decide whether `body` is parsed as
+    /// `application/x-www-form-urlencoded` or `multipart/form-data`.
+    pub content_type: String,
+
+    /// Query string arguments, decoded by the `args` module.
+    pub args_get: MultiMap,
+    /// `application/x-www-form-urlencoded` body arguments, decoded by the `args` module, plus
+    /// the non-file parts of a `multipart/form-data` body, appended by the `multipart` module.
+    pub args_post: MultiMap,
+
+    /// `(field name, original filename)` pairs for every uploaded file, populated by the
+    /// `multipart` module.
+    pub files: MultiMap,
+    /// The summed byte length of every uploaded file's body, i.e. `FILES_COMBINED_SIZE`.
+    pub files_combined_size: usize,
+    /// `(part name, raw header line)` pairs for every header of every multipart part, i.e.
+    /// `MULTIPART_PART_HEADERS`.
+    pub multipart_part_headers: MultiMap,
+
+    pub response_status: Option<u16>,
+    pub response_body: Vec<u8>,
+
+    /// The transient TX collection. `TX:0` holds the last @rx match, `TX:1`-`TX:9` hold
+    /// capturing-parens subexpressions, both reserved for use by the `capture` action.
+    pub tx: HashMap<String, String>,
+
+    matched_var: Option<String>,
+    matched_var_name: Option<String>,
+    matched_vars: Vec<String>,
+    matched_vars_names: Vec<String>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs the `args`, `cookies`, and (for `multipart/form-data` requests) `multipart`
+    /// parsers over the transaction's raw `query_string`/`headers`/`body`, populating
+    /// `args_get`, `args_post`, `cookies`, `files`, `files_combined_size`, and
+    /// `multipart_part_headers`. This gives rules like `ARGS_GET:id`, `REQUEST_COOKIES`, and
+    /// `FILES_NAMES` something concrete to match against. Must be called once, after the raw
+    /// request fields are set and before the transaction is evaluated against any `SecRule` --
+    /// `multipart::populate` appends to `args_post`/`files` rather than replacing them, so
+    /// calling this more than once would double-count multipart parts.
+    pub fn populate(&mut self) {
+        crate::args::populate(self);
+        crate::cookies::populate(self);
+
+        if let Some(boundary) = crate::multipart::boundary(&self.content_type) {
+            let body = self.body.clone();
+            crate::multipart::populate(self, &boundary, &body);
+        }
+    }
+
+    /// Returns the value of the first request header named `name`, case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Stores an operator's capture groups into the reserved `TX:0`-`TX:9` slots, clearing any
+    /// slot left over from a previous match that the new captures didn't fill.
+    pub fn set_captures(&mut self, captures: &[String]) {
+        for i in 0..=9 {
+            let key = i.to_string();
+            match captures.get(i) {
+                Some(capture) => {
+                    self.tx.insert(key, capture.clone());
+                }
+                None => {
+                    self.tx.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Records a match against `var_name`/`value`, updating `MATCHED_VAR`/`MATCHED_VAR_NAME`
+    /// and appending to the `MATCHED_VARS`/`MATCHED_VARS_NAMES` collections.
+    pub fn record_match(&mut self, var_name: &str, value: &str) {
+        self.matched_var = Some(value.to_owned());
+        self.matched_var_name = Some(var_name.to_owned());
+        self.matched_vars.push(value.to_owned());
+        self.matched_vars_names.push(var_name.to_owned());
+    }
+
+    /// Clears the `MATCHED_VAR*` collections ahead of evaluating a new rule.
+    pub fn clear_matches(&mut self) {
+        self.matched_var = None;
+        self.matched_var_name = None;
+        self.matched_vars.clear();
+        self.matched_vars_names.clear();
+    }
+
+    pub fn matched_var(&self) -> Option<&str> {
+        self.matched_var.as_deref()
+    }
+
+    pub fn matched_var_name(&self) -> Option<&str> {
+        self.matched_var_name.as_deref()
+    }
+
+    pub fn matched_vars(&self) -> &[String] {
+        &self.matched_vars
+    }
+
+    pub fn matched_vars_names(&self) -> &[String] {
+        &self.matched_vars_names
+    }
+}